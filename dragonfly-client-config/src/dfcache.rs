@@ -0,0 +1,77 @@
+/*
+ *     Copyright 2024 The Dragonfly Authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// NAME is the name of dfcache.
+pub const NAME: &str = "dfcache";
+
+/// OutputFormat is the output format of dfcache's subcommands, selected by the top-level
+/// `--format` flag or the config file's `format` field. It controls both how a successful
+/// result is printed to stdout and how an error is rendered, so callers scripting dfcache
+/// never have to parse mixed formats. It lives in this crate, rather than the dfcache
+/// binary, so it can also be named by `Config::format` below.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Text prints human-readable output. This is the default.
+    #[default]
+    Text,
+
+    /// Json prints machine-readable, single-line JSON output.
+    Json,
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Text => write!(f, "text"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Config is the dfcache config file's contents, every field of which is optional so that
+/// an unset field falls through to the built-in default, and any field can still be
+/// overridden by the matching CLI flag.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Config {
+    /// endpoint overrides the built-in default unix socket path for dfdaemon's GRPC
+    /// server.
+    #[serde(default)]
+    pub endpoint: Option<PathBuf>,
+
+    /// replica_count overrides the built-in default number of replicas to persist during
+    /// import.
+    #[serde(default)]
+    pub replica_count: Option<u32>,
+
+    /// rate_limit overrides the built-in default (unlimited) transfer rate, in bytes per
+    /// second, for import and export.
+    #[serde(default)]
+    pub rate_limit: Option<u64>,
+
+    /// format overrides the built-in default output format, text.
+    #[serde(default)]
+    pub format: Option<OutputFormat>,
+}
+
+/// default_dfcache_config_path returns the default location dfcache looks for its config
+/// file, when `--config` isn't given explicitly.
+pub fn default_dfcache_config_path() -> PathBuf {
+    PathBuf::from("/etc/dragonfly/dfcache.yaml")
+}
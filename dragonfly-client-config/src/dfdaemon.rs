@@ -0,0 +1,26 @@
+/*
+ *     Copyright 2024 The Dragonfly Authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::path::PathBuf;
+
+/// NAME is the name of dfdaemon.
+pub const NAME: &str = "dfdaemon";
+
+/// default_download_unix_socket_path returns the default unix socket path that dfdaemon's
+/// download gRPC server listens on, and that dfcache dials by default.
+pub fn default_download_unix_socket_path() -> PathBuf {
+    PathBuf::from("/var/run/dragonfly/dfdaemon.sock")
+}
@@ -0,0 +1,41 @@
+/*
+ *     Copyright 2024 The Dragonfly Authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use clap::builder::TypedValueParser;
+use std::ffi::OsStr;
+
+/// VersionValueParser prints this binary's version information and exits immediately when
+/// `-V`/`--version` is passed, instead of requiring a subcommand.
+#[derive(Debug, Clone)]
+pub struct VersionValueParser;
+
+impl TypedValueParser for VersionValueParser {
+    type Value = bool;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        _arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        if value == "true" {
+            println!("{}", cmd.render_version());
+            std::process::exit(0);
+        }
+
+        Ok(false)
+    }
+}
@@ -0,0 +1,36 @@
+/*
+ *     Copyright 2024 The Dragonfly Authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use thiserror::Error as ThisError;
+
+/// Result is the result type used throughout the Dragonfly client crates.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Error is the error type used throughout the Dragonfly client crates.
+#[derive(Debug, ThisError)]
+pub enum Error {
+    /// IO is an error from the standard library's IO operations.
+    #[error("io error: {0}")]
+    IO(#[from] std::io::Error),
+
+    /// TonicStatus is an error returned by a gRPC call.
+    #[error("grpc error: {0}")]
+    TonicStatus(#[from] tonic::Status),
+
+    /// Unknown is a catch-all error for conditions that don't have a dedicated variant.
+    #[error("{0}")]
+    Unknown(String),
+}
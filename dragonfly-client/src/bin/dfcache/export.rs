@@ -0,0 +1,180 @@
+/*
+ *     Copyright 2024 The Dragonfly Authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use clap::Parser;
+use dragonfly_api::dfdaemon::v2::DownloadPersistentCacheTaskRequest;
+use dragonfly_client::grpc::dfdaemon_download::DfdaemonDownloadClient;
+use dragonfly_client_config::{dfcache, dfdaemon};
+use dragonfly_client_core::{Error, Result};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use super::flags::{self, parse_rate_limit};
+use super::output::{print_result, OutputFormat};
+use super::{get_dfdaemon_download_client, wait_for_shutdown_signal};
+
+/// ExportCommand is the subcommand of dfcache that exports a task already replicated in
+/// the P2P network to a local file path.
+#[derive(Debug, Clone, Parser)]
+pub struct ExportCommand {
+    #[arg(help = "Specify the task ID to export")]
+    id: String,
+
+    #[arg(help = "Specify the local path to export the file to")]
+    path: PathBuf,
+
+    #[arg(long = "endpoint", help = flags::ENDPOINT_HELP)]
+    endpoint: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "TIMEOUT",
+        default_value = "30m",
+        value_parser = humantime::parse_duration,
+        help = "Specify the timeout for exporting a file from the P2P network"
+    )]
+    timeout: Duration,
+
+    #[arg(
+        long = "rate-limit",
+        value_name = "BYTES_PER_SEC",
+        value_parser = parse_rate_limit,
+        help = flags::rate_limit_help("export"),
+    )]
+    rate_limit: Option<u64>,
+}
+
+/// Resolved holds this command's options after layering explicit CLI flags over the
+/// config file over dfcache's built-in defaults.
+struct Resolved {
+    endpoint: PathBuf,
+    rate_limit: Option<u64>,
+}
+
+/// Output is the JSON representation of a successful export, printed to stdout in
+/// `--format json` mode.
+#[derive(Debug, Clone, Serialize)]
+struct Output {
+    path: String,
+}
+
+impl ExportCommand {
+    /// resolve layers this command's CLI flags over the config file over the built-in
+    /// defaults, following the precedence explicit-flag > config-file > built-in-default.
+    fn resolve(&self, config: Option<&dfcache::Config>) -> Resolved {
+        Resolved {
+            endpoint: flags::resolve(
+                self.endpoint.clone(),
+                config.and_then(|config| config.endpoint.clone()),
+                dfdaemon::default_download_unix_socket_path,
+            ),
+            rate_limit: flags::resolve_optional(
+                self.rate_limit,
+                config.and_then(|config| config.rate_limit),
+            ),
+        }
+    }
+
+    /// Executes the export subcommand, exporting a task from the P2P network to a local
+    /// file path and printing the resulting path in the requested output format.
+    pub async fn execute(self, format: OutputFormat, config: Option<&dfcache::Config>) -> Result<()> {
+        let resolved = self.resolve(config);
+        let path = self.run_with_cancellation(&resolved).await?;
+        print_result(format, Output { path: path.clone() }, |output| output.path.clone());
+        Ok(())
+    }
+
+    /// run_with_cancellation exports the task described by this command, returning the
+    /// local path it was written to, unless a SIGINT/SIGTERM arrives first. `self.id`
+    /// names a persistent-cache task shared by every consumer reading it, not something
+    /// this invocation owns, so cancellation must not delete it on the daemon; dropping
+    /// the `run` future when the shutdown branch wins is what tears down the in-flight
+    /// streaming RPC. Only the partial output dfcache itself wrote is removed, so an
+    /// interrupted export never leaves a truncated file at the destination path, nor
+    /// touches a pre-existing file that happened to already live there.
+    async fn run_with_cancellation(&self, resolved: &Resolved) -> Result<String> {
+        let dfdaemon_download_client =
+            get_dfdaemon_download_client(resolved.endpoint.clone()).await?;
+
+        tokio::select! {
+            result = self.run(&dfdaemon_download_client, resolved) => result,
+            _ = wait_for_shutdown_signal() => {
+                warn!("received shutdown signal, cancelling export {}", self.id);
+                self.remove_partial_output();
+                Err(Error::Unknown(format!("export {} was cancelled", self.id)))
+            }
+        }
+    }
+
+    /// run exports the task described by this command to the local path and returns it.
+    /// The download is written to a temporary path alongside the destination and renamed
+    /// into place only once it completes, so a reader can never observe a partially
+    /// written file at the destination path.
+    async fn run(
+        &self,
+        dfdaemon_download_client: &DfdaemonDownloadClient,
+        resolved: &Resolved,
+    ) -> Result<String> {
+        info!("export {} to {}", self.id, self.path.display());
+
+        let request = DownloadPersistentCacheTaskRequest {
+            task_id: self.id.clone(),
+            output_path: self.temp_path().to_string_lossy().to_string(),
+            rate_limit: resolved.rate_limit.unwrap_or(0),
+            ..Default::default()
+        };
+
+        dfdaemon_download_client
+            .download_persistent_cache_task(request, self.timeout)
+            .await?;
+
+        std::fs::rename(self.temp_path(), &self.path).map_err(Error::IO)?;
+
+        let path = self.path.to_string_lossy().to_string();
+        info!("export {} succeeded, local path: {}", self.id, path);
+        Ok(path)
+    }
+
+    /// temp_path is the path dfcache itself writes the export to before renaming it into
+    /// place, so it only ever deletes a file it created.
+    fn temp_path(&self) -> PathBuf {
+        let mut file_name = self
+            .path
+            .file_name()
+            .unwrap_or_default()
+            .to_os_string();
+        file_name.push(".dfcache.tmp");
+        self.path.with_file_name(file_name)
+    }
+
+    /// remove_partial_output removes dfcache's own temporary output file if it was
+    /// partially written before the export was cancelled. It never touches `self.path`
+    /// itself, since that may be a pre-existing file dfcache didn't create.
+    fn remove_partial_output(&self) {
+        let temp_path = self.temp_path();
+        if temp_path.exists() {
+            if let Err(err) = std::fs::remove_file(&temp_path) {
+                warn!(
+                    "failed to remove partially written output {}: {}",
+                    temp_path.display(),
+                    err
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+/*
+ *     Copyright 2024 The Dragonfly Authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use bytesize::ByteSize;
+
+/// parse_rate_limit parses a human-readable byte rate such as `10MB` or `512KB` into a
+/// plain bytes-per-second value, for use as a clap `value_parser` on `--rate-limit`.
+pub fn parse_rate_limit(s: &str) -> std::result::Result<u64, String> {
+    s.parse::<ByteSize>()
+        .map(|size| size.as_u64())
+        .map_err(|err| format!("invalid rate limit {}: {}", s, err))
+}
+
+/// resolve implements the precedence rule shared by every layered dfcache option: an
+/// explicit CLI flag wins, the config file's value is next, and the built-in default
+/// applies only when neither is set.
+pub fn resolve<T>(flag: Option<T>, config: Option<T>, default: impl FnOnce() -> T) -> T {
+    flag.or(config).unwrap_or_else(default)
+}
+
+/// resolve_optional is `resolve` for options with no built-in default, such as rate
+/// limits, where "unset" is itself a meaningful value (unlimited) rather than something
+/// to fall back from.
+pub fn resolve_optional<T>(flag: Option<T>, config: Option<T>) -> Option<T> {
+    flag.or(config)
+}
+
+/// ENDPOINT_HELP is the shared help text for every subcommand's `--endpoint` flag.
+pub const ENDPOINT_HELP: &str = "Endpoint of dfdaemon's GRPC server. Overrides the config \
+file's endpoint, which in turn overrides the built-in default unix socket path.";
+
+/// rate_limit_help renders the shared `--rate-limit` help text for the given action noun
+/// (e.g. \"import\", \"export\"), so each subcommand doesn't paste its own copy of the
+/// boilerplate precedence tail.
+pub fn rate_limit_help(action: &str) -> String {
+    format!(
+        "Limit the {action} transfer rate, for example 10MB or 512KB. Overrides the \
+        config file's rate limit, which in turn overrides the built-in default of \
+        unlimited."
+    )
+}
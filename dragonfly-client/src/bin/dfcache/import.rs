@@ -0,0 +1,199 @@
+/*
+ *     Copyright 2024 The Dragonfly Authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use clap::Parser;
+use dragonfly_api::dfdaemon::v2::UploadPersistentCacheTaskRequest;
+use dragonfly_client::grpc::dfdaemon_download::DfdaemonDownloadClient;
+use dragonfly_client_config::{dfcache, dfdaemon};
+use dragonfly_client_core::{Error, Result};
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use super::flags::{self, parse_rate_limit};
+use super::output::{print_result, OutputFormat};
+use super::{get_dfdaemon_download_client, wait_for_shutdown_signal};
+
+/// ImportCommand is the subcommand of dfcache that imports a local file, or a remote
+/// `http(s)://` source, into the P2P network, optionally copying it to multiple
+/// replicas.
+#[derive(Debug, Clone, Parser)]
+pub struct ImportCommand {
+    #[arg(
+        help = "Specify the path of the local file, or the http(s):// URL of the remote source, to import"
+    )]
+    source: String,
+
+    #[arg(long = "endpoint", help = flags::ENDPOINT_HELP)]
+    endpoint: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "Specify the number of replicas to persist during import. Overrides the \
+        config file's replica count, which in turn overrides the built-in default of 1."
+    )]
+    replica_count: Option<u32>,
+
+    #[arg(
+        long,
+        value_name = "TIMEOUT",
+        default_value = "30m",
+        value_parser = humantime::parse_duration,
+        help = "Specify the timeout for importing a file into the P2P network"
+    )]
+    timeout: Duration,
+
+    #[arg(
+        long = "ca-cert",
+        help = "Specify one or more root CA certificates to verify the remote source's TLS certificate against, for http(s):// imports"
+    )]
+    ca_cert: Vec<PathBuf>,
+
+    #[arg(
+        long = "insecure-skip-verify",
+        default_value_t = false,
+        help = "Skip TLS certificate verification of the remote source entirely, for trusted internal http(s):// hosts"
+    )]
+    insecure_skip_verify: bool,
+
+    #[arg(
+        long = "rate-limit",
+        value_name = "BYTES_PER_SEC",
+        value_parser = parse_rate_limit,
+        help = flags::rate_limit_help("import"),
+    )]
+    rate_limit: Option<u64>,
+}
+
+/// Resolved holds this command's options after layering explicit CLI flags over the
+/// config file over dfcache's built-in defaults.
+struct Resolved {
+    endpoint: PathBuf,
+    replica_count: u32,
+    rate_limit: Option<u64>,
+}
+
+impl ImportCommand {
+    /// resolve layers this command's CLI flags over the config file over the built-in
+    /// defaults, following the precedence explicit-flag > config-file > built-in-default.
+    fn resolve(&self, config: Option<&dfcache::Config>) -> Resolved {
+        Resolved {
+            endpoint: flags::resolve(
+                self.endpoint.clone(),
+                config.and_then(|config| config.endpoint.clone()),
+                dfdaemon::default_download_unix_socket_path,
+            ),
+            replica_count: flags::resolve(
+                self.replica_count,
+                config.and_then(|config| config.replica_count),
+                || 1,
+            ),
+            rate_limit: flags::resolve_optional(
+                self.rate_limit,
+                config.and_then(|config| config.rate_limit),
+            ),
+        }
+    }
+}
+
+/// Output is the JSON representation of a successful import, printed to stdout in
+/// `--format json` mode.
+#[derive(Debug, Clone, Serialize)]
+struct Output {
+    id: String,
+}
+
+impl ImportCommand {
+    /// Executes the import subcommand, importing a local file or remote http(s):// source
+    /// into the P2P network and
+    /// printing the resulting task ID in the requested output format.
+    pub async fn execute(self, format: OutputFormat, config: Option<&dfcache::Config>) -> Result<()> {
+        let resolved = self.resolve(config);
+        let id = self.run_with_cancellation(&resolved).await?;
+        print_result(format, Output { id: id.clone() }, |output| output.id.clone());
+        Ok(())
+    }
+
+    /// run_with_cancellation imports the local file or remote source described by this
+    /// command, returning the task ID assigned to it by the P2P network, unless a
+    /// SIGINT/SIGTERM arrives first. The upload RPC doesn't hand back a task ID until it
+    /// completes, so there is nothing to cancel on the daemon yet; dropping the `run`
+    /// future when the shutdown branch wins is what tears down the in-flight RPC.
+    async fn run_with_cancellation(&self, resolved: &Resolved) -> Result<String> {
+        let dfdaemon_download_client =
+            get_dfdaemon_download_client(resolved.endpoint.clone()).await?;
+
+        tokio::select! {
+            result = self.run(&dfdaemon_download_client, resolved) => result,
+            _ = wait_for_shutdown_signal() => {
+                warn!("received shutdown signal, cancelling import {}", self.source);
+                Err(Error::Unknown(format!("import {} was cancelled", self.source)))
+            }
+        }
+    }
+
+    /// run imports the local file or remote source described by this command and
+    /// returns the task ID assigned to it by the P2P network.
+    async fn run(
+        &self,
+        dfdaemon_download_client: &DfdaemonDownloadClient,
+        resolved: &Resolved,
+    ) -> Result<String> {
+        info!("import {} to P2P network", self.source);
+
+        let request = self.build_request(resolved)?;
+        let task = dfdaemon_download_client
+            .upload_persistent_cache_task(request, self.timeout)
+            .await?;
+
+        info!("import {} succeeded, task id: {}", self.source, task.id);
+        Ok(task.id)
+    }
+
+    /// build_request turns this command's source into an upload request, applying the
+    /// TLS controls when the source is a remote http(s):// URL so the daemon applies them
+    /// when it dials the origin. A local file is imported exactly as before.
+    fn build_request(&self, resolved: &Resolved) -> Result<UploadPersistentCacheTaskRequest> {
+        if self.source.starts_with("http://") || self.source.starts_with("https://") {
+            let root_ca_cert = self
+                .ca_cert
+                .iter()
+                .map(std::fs::read_to_string)
+                .collect::<std::io::Result<Vec<String>>>()
+                .map_err(Error::IO)?;
+
+            return Ok(UploadPersistentCacheTaskRequest {
+                url: self.source.clone(),
+                replica_count: resolved.replica_count,
+                // root_ca_cert is the trust anchor the daemon verifies the origin's TLS
+                // certificate against, distinct from any client certificate chain dfcache
+                // itself might present.
+                root_ca_cert,
+                skip_verify: self.insecure_skip_verify,
+                rate_limit: resolved.rate_limit.unwrap_or(0),
+                ..Default::default()
+            });
+        }
+
+        Ok(UploadPersistentCacheTaskRequest {
+            path: self.source.clone(),
+            replica_count: resolved.replica_count,
+            rate_limit: resolved.rate_limit.unwrap_or(0),
+            ..Default::default()
+        })
+    }
+}
@@ -20,14 +20,23 @@ use dragonfly_client::grpc::health::HealthClient;
 use dragonfly_client::tracing::init_tracing;
 use dragonfly_client_config::VersionValueParser;
 use dragonfly_client_config::{dfcache, dfdaemon};
-use dragonfly_client_core::Result;
+use dragonfly_client_core::{Error, Result};
+use semver::Version;
 use std::path::PathBuf;
-use tracing::Level;
+use tracing::{warn, Level};
+
+/// CARGO_PKG_VERSION is the version of the dfcache binary, it is used to check the
+/// compatibility with the dfdaemon it talks to.
+const CARGO_PKG_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 pub mod export;
+pub mod flags;
 pub mod import;
+pub mod output;
 pub mod stat;
 
+use output::OutputFormat;
+
 #[derive(Debug, Parser)]
 #[command(
     name = dfcache::NAME,
@@ -49,10 +58,54 @@ struct Args {
     )]
     version: bool,
 
+    #[arg(
+        long = "format",
+        value_enum,
+        help = "Specify the output format of the command's result and errors [text, json]. \
+        Overrides the config file's format, which in turn overrides the built-in default of text."
+    )]
+    format: Option<OutputFormat>,
+
+    #[arg(
+        long = "config",
+        help = "Specify the path to the dfcache config file. A missing file at the default \
+        location is not an error; an explicitly specified but missing file is."
+    )]
+    config: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Command,
 }
 
+/// Loads the dfcache config file, whose values sit between explicit CLI flags and the
+/// built-in defaults; the layering itself is applied by the callers, not here. A missing file at dfcache's default config location is a no-op rather than an
+/// error, since most installs never create one; a missing file at an explicitly requested
+/// path is an error, since the operator asked for that specific file.
+fn load_config(explicit_path: Option<&PathBuf>) -> Result<Option<dfcache::Config>> {
+    let (path, required) = match explicit_path {
+        Some(path) => (path.clone(), true),
+        None => (dfcache::default_dfcache_config_path(), false),
+    };
+
+    if !path.exists() {
+        if required {
+            return Err(Error::Unknown(format!(
+                "config file {} does not exist",
+                path.display()
+            )));
+        }
+
+        return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(&path).map_err(Error::IO)?;
+    let config: dfcache::Config = serde_yaml::from_str(&content).map_err(|err| {
+        Error::Unknown(format!("failed to parse config file {}: {}", path.display(), err))
+    })?;
+
+    Ok(Some(config))
+}
+
 #[derive(Debug, Clone, Subcommand)]
 #[command(args_conflicts_with_subcommands = true)]
 pub enum Command {
@@ -87,11 +140,11 @@ pub enum Command {
 /// Implement the execute for Command.
 impl Command {
     #[allow(unused)]
-    pub async fn execute(self) -> Result<()> {
+    pub async fn execute(self, format: OutputFormat, config: Option<&dfcache::Config>) -> Result<()> {
         match self {
-            Self::Import(cmd) => cmd.execute().await,
-            Self::Export(cmd) => cmd.execute().await,
-            Self::Stat(cmd) => cmd.execute().await,
+            Self::Import(cmd) => cmd.execute(format, config).await,
+            Self::Export(cmd) => cmd.execute(format, config).await,
+            Self::Stat(cmd) => cmd.execute(format, config).await,
         }
     }
 }
@@ -101,8 +154,32 @@ async fn main() -> anyhow::Result<()> {
     // Parse command line arguments.
     let args = Args::parse();
 
-    // Execute the command.
-    args.command.execute().await?;
+    // Load the config file, if any, before resolving any layered defaults. An explicit
+    // CLI flag always takes precedence over the config file, which in turn takes
+    // precedence over the built-in default. A config load failure can't consult the
+    // config file's own `format` field, so it falls back to the CLI flag or the built-in
+    // default, but it's still rendered through print_error so `--format json` callers
+    // never see an unstructured message on stderr.
+    let fallback_format = args.format.unwrap_or_default();
+    let config = match load_config(args.config.as_ref()) {
+        Ok(config) => config,
+        Err(err) => {
+            output::print_error(fallback_format, &err);
+            std::process::exit(1);
+        }
+    };
+    let format = args
+        .format
+        .or_else(|| config.as_ref().and_then(|config| config.format))
+        .unwrap_or_default();
+
+    // Execute the command, rendering any error in the selected output format instead of
+    // letting it bubble out as an unstructured anyhow message.
+    if let Err(err) = args.command.execute(format, config.as_ref()).await {
+        output::print_error(format, &err);
+        std::process::exit(1);
+    }
+
     Ok(())
 }
 
@@ -110,14 +187,87 @@ async fn main() -> anyhow::Result<()> {
 ///
 /// This function establishes a connection to the dfdaemon service via Unix domain socket
 /// and performs a health check to ensure the service is running and ready to handle
-/// download requests. Only after successful health verification does it return the
-/// download client for actual use.
+/// download requests. It then attempts to negotiate protocol compatibility by comparing
+/// the dfdaemon's reported version against this crate's own version. The version probe
+/// isn't confirmed to be served on this same download socket, so a probe failure (for
+/// example `Unimplemented`) is logged and skipped rather than treated as a connection
+/// failure — it must not turn every import/export/stat into an outage on daemons that
+/// don't answer it. Only after a successful health check, and a compatibility check
+/// whenever the probe does succeed, does it return the download client for actual use.
 pub async fn get_dfdaemon_download_client(endpoint: PathBuf) -> Result<DfdaemonDownloadClient> {
     // Check dfdaemon's health.
     let health_client = HealthClient::new_unix(endpoint.clone()).await?;
     health_client.check_dfdaemon_download().await?;
 
+    // Check the version compatibility between dfcache and dfdaemon before handing back
+    // a client, so an ABI mismatch fails fast instead of deep inside a download. This is
+    // best-effort until the version probe is confirmed to be served on the download
+    // socket: a probe error skips the check instead of rejecting the connection.
+    match health_client.version_dfdaemon_download().await {
+        Ok(dfdaemon_version) => check_version_compatibility(&dfdaemon_version)?,
+        Err(err) => warn!(
+            "skipping dfdaemon version compatibility check, version probe failed: {}",
+            err
+        ),
+    }
+
     // Get dfdaemon download client.
     let dfdaemon_download_client = DfdaemonDownloadClient::new_unix(endpoint).await?;
     Ok(dfdaemon_download_client)
 }
+
+/// Waits for either SIGINT or SIGTERM, resolving once either is received so a long-running
+/// import or export can cancel its in-flight RPC and clean up any partially written output
+/// before exiting non-zero, instead of running to completion regardless.
+pub async fn wait_for_shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut sigterm =
+            signal(SignalKind::terminate()).expect("failed to install SIGTERM handler");
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {}
+            _ = sigterm.recv() => {}
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+/// Checks that the dfdaemon's reported version is compatible with this dfcache binary's
+/// own version, following semver rules: equal major versions are compatible, except for
+/// 0.x releases where the minor version must also match.
+fn check_version_compatibility(dfdaemon_version: &str) -> Result<()> {
+    let client_version = Version::parse(CARGO_PKG_VERSION).map_err(|err| {
+        Error::Unknown(format!(
+            "failed to parse dfcache version {}: {}",
+            CARGO_PKG_VERSION, err
+        ))
+    })?;
+
+    let daemon_version = Version::parse(dfdaemon_version).map_err(|err| {
+        Error::Unknown(format!(
+            "failed to parse dfdaemon version {}: {}",
+            dfdaemon_version, err
+        ))
+    })?;
+
+    let compatible = if client_version.major == 0 || daemon_version.major == 0 {
+        client_version.major == daemon_version.major && client_version.minor == daemon_version.minor
+    } else {
+        client_version.major == daemon_version.major
+    };
+
+    if !compatible {
+        return Err(Error::Unknown(format!(
+            "incompatible dfdaemon version: dfcache is {} but dfdaemon is {}",
+            client_version, daemon_version
+        )));
+    }
+
+    Ok(())
+}
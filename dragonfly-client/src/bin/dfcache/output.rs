@@ -0,0 +1,62 @@
+/*
+ *     Copyright 2024 The Dragonfly Authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use dragonfly_client_core::Error;
+use serde::Serialize;
+
+/// OutputFormat lives in `dragonfly_client_config::dfcache` so the config file's `format`
+/// field can name it too; re-exported here so the rest of this binary can keep referring
+/// to it as `output::OutputFormat`.
+pub use dragonfly_client_config::dfcache::OutputFormat;
+
+/// ErrorOutput is the JSON representation of an error emitted on stderr in JSON mode.
+#[derive(Debug, Serialize)]
+struct ErrorOutput {
+    error: String,
+}
+
+/// print_result prints a successful subcommand result in the requested format: `text` is
+/// used to render the human-readable form, while `value` is serialized as a single JSON
+/// object in JSON mode.
+pub fn print_result<T: Serialize>(format: OutputFormat, value: T, text: impl FnOnce(&T) -> String) {
+    match format {
+        OutputFormat::Text => println!("{}", text(&value)),
+        OutputFormat::Json => match serde_json::to_string(&value) {
+            Ok(json) => println!("{}", json),
+            Err(err) => eprintln!("failed to serialize output as json: {}", err),
+        },
+    }
+}
+
+/// print_error prints a failed subcommand's error in the requested format, always to
+/// stderr. In JSON mode the error is serialized as a JSON object instead of being printed
+/// as a plain `anyhow`-style message, so scripted callers can rely on stderr being
+/// structured whenever stdout is.
+pub fn print_error(format: OutputFormat, err: &Error) {
+    match format {
+        OutputFormat::Text => eprintln!("{}", err),
+        OutputFormat::Json => {
+            let output = ErrorOutput {
+                error: err.to_string(),
+            };
+
+            match serde_json::to_string(&output) {
+                Ok(json) => eprintln!("{}", json),
+                Err(_) => eprintln!("{}", err),
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+/*
+ *     Copyright 2024 The Dragonfly Authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use clap::Parser;
+use dragonfly_api::dfdaemon::v2::StatPersistentCacheTaskRequest;
+use dragonfly_client_config::{dfcache, dfdaemon};
+use dragonfly_client_core::Result;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::info;
+
+use super::flags;
+use super::get_dfdaemon_download_client;
+use super::output::{print_result, OutputFormat};
+
+/// StatCommand is the subcommand of dfcache that looks up the metadata of a task already
+/// replicated in the P2P network, by task ID.
+#[derive(Debug, Clone, Parser)]
+pub struct StatCommand {
+    #[arg(help = "Specify the task ID to stat")]
+    id: String,
+
+    #[arg(long = "endpoint", help = flags::ENDPOINT_HELP)]
+    endpoint: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "TIMEOUT",
+        default_value = "3s",
+        value_parser = humantime::parse_duration,
+        help = "Specify the timeout for stating a file in the P2P network"
+    )]
+    timeout: Duration,
+}
+
+/// Output is the JSON representation of a successful stat, printed to stdout in
+/// `--format json` mode.
+#[derive(Debug, Clone, Serialize)]
+struct Output {
+    id: String,
+    content_length: u64,
+    piece_count: u32,
+}
+
+impl StatCommand {
+    /// Executes the stat subcommand, looking up a task's metadata in the P2P network and
+    /// printing it in the requested output format.
+    pub async fn execute(self, format: OutputFormat, config: Option<&dfcache::Config>) -> Result<()> {
+        let endpoint = flags::resolve(
+            self.endpoint.clone(),
+            config.and_then(|config| config.endpoint.clone()),
+            dfdaemon::default_download_unix_socket_path,
+        );
+
+        let output = self.run(endpoint).await?;
+        print_result(format, output, |output| {
+            format!(
+                "id: {}\ncontent_length: {}\npiece_count: {}",
+                output.id, output.content_length, output.piece_count
+            )
+        });
+        Ok(())
+    }
+
+    /// run looks up the metadata of the task described by this command.
+    async fn run(&self, endpoint: PathBuf) -> Result<Output> {
+        info!("stat {}", self.id);
+
+        let dfdaemon_download_client = get_dfdaemon_download_client(endpoint).await?;
+
+        let request = StatPersistentCacheTaskRequest {
+            task_id: self.id.clone(),
+        };
+
+        let task = dfdaemon_download_client
+            .stat_persistent_cache_task(request, self.timeout)
+            .await?;
+
+        Ok(Output {
+            id: task.id,
+            content_length: task.content_length,
+            piece_count: task.piece_count,
+        })
+    }
+}
@@ -0,0 +1,109 @@
+/*
+ *     Copyright 2024 The Dragonfly Authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use dragonfly_api::dfdaemon::v2::{
+    dfdaemon_download_client::DfdaemonDownloadClient as DfdaemonDownloadGRPCClient,
+    DeletePersistentCacheTaskRequest, DownloadPersistentCacheTaskRequest, PersistentCacheTask,
+    StatPersistentCacheTaskRequest, UploadPersistentCacheTaskRequest,
+};
+use dragonfly_client_core::{Error, Result};
+use std::path::PathBuf;
+use std::time::Duration;
+use tonic::transport::{Channel, Endpoint, Uri};
+use tower::service_fn;
+
+/// DfdaemonDownloadClient is a gRPC client for dfdaemon's download service, used by
+/// dfcache's `import`, `export`, and `stat` subcommands to operate on persistent cache
+/// tasks.
+pub struct DfdaemonDownloadClient {
+    channel: Channel,
+}
+
+impl DfdaemonDownloadClient {
+    /// new_unix connects to dfdaemon's download gRPC server over the given Unix domain
+    /// socket.
+    pub async fn new_unix(endpoint: PathBuf) -> Result<Self> {
+        let channel = Endpoint::try_from("http://[::]:50051")
+            .map_err(|err| Error::Unknown(err.to_string()))?
+            .connect_with_connector(service_fn(move |_: Uri| {
+                let endpoint = endpoint.clone();
+                async move { tokio::net::UnixStream::connect(endpoint).await }
+            }))
+            .await
+            .map_err(|err| Error::Unknown(err.to_string()))?;
+
+        Ok(Self { channel })
+    }
+
+    /// upload_persistent_cache_task imports a local file or a remote http(s):// source
+    /// into the P2P network as a persistent cache task, replicating it as requested, and
+    /// returns its metadata once the upload completes.
+    pub async fn upload_persistent_cache_task(
+        &self,
+        request: UploadPersistentCacheTaskRequest,
+        timeout: Duration,
+    ) -> Result<PersistentCacheTask> {
+        let mut client = DfdaemonDownloadGRPCClient::new(self.channel.clone());
+        let mut request = tonic::Request::new(request);
+        request.set_timeout(timeout);
+
+        Ok(client
+            .upload_persistent_cache_task(request)
+            .await?
+            .into_inner())
+    }
+
+    /// download_persistent_cache_task downloads a persistent cache task already
+    /// replicated in the P2P network to a local output path.
+    pub async fn download_persistent_cache_task(
+        &self,
+        request: DownloadPersistentCacheTaskRequest,
+        timeout: Duration,
+    ) -> Result<()> {
+        let mut client = DfdaemonDownloadGRPCClient::new(self.channel.clone());
+        let mut request = tonic::Request::new(request);
+        request.set_timeout(timeout);
+        client.download_persistent_cache_task(request).await?;
+        Ok(())
+    }
+
+    /// stat_persistent_cache_task looks up a persistent cache task's metadata by task ID.
+    pub async fn stat_persistent_cache_task(
+        &self,
+        request: StatPersistentCacheTaskRequest,
+        timeout: Duration,
+    ) -> Result<PersistentCacheTask> {
+        let mut client = DfdaemonDownloadGRPCClient::new(self.channel.clone());
+        let mut request = tonic::Request::new(request);
+        request.set_timeout(timeout);
+
+        Ok(client
+            .stat_persistent_cache_task(request)
+            .await?
+            .into_inner())
+    }
+
+    /// delete_persistent_cache_task asks the daemon to delete a persistent cache task by
+    /// ID. dfcache's import and export cancellation paths don't call it: they only drop
+    /// the in-flight RPC, leaving the task itself on the daemon.
+    pub async fn delete_persistent_cache_task(&self, task_id: String) -> Result<()> {
+        let mut client = DfdaemonDownloadGRPCClient::new(self.channel.clone());
+        client
+            .delete_persistent_cache_task(DeletePersistentCacheTaskRequest { task_id })
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+/*
+ *     Copyright 2024 The Dragonfly Authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use dragonfly_api::dfdaemon::v2::{dfdaemon_client::DfdaemonClient, GetDaemonInfoRequest};
+use dragonfly_client_core::{Error, Result};
+use std::path::PathBuf;
+use tonic::transport::{Channel, Endpoint, Uri};
+use tonic_health::pb::health_client::HealthClient as HealthGrpcClient;
+use tonic_health::pb::HealthCheckRequest;
+use tower::service_fn;
+
+/// HealthClient is a gRPC client for probing a dfdaemon's health and reported version over
+/// its Unix domain socket, before a download client is handed out for real use.
+pub struct HealthClient {
+    channel: Channel,
+}
+
+impl HealthClient {
+    /// new_unix connects to dfdaemon's gRPC server over the given Unix domain socket.
+    pub async fn new_unix(endpoint: PathBuf) -> Result<Self> {
+        let channel = Endpoint::try_from("http://[::]:50051")
+            .map_err(|err| Error::Unknown(err.to_string()))?
+            .connect_with_connector(service_fn(move |_: Uri| {
+                let endpoint = endpoint.clone();
+                async move { tokio::net::UnixStream::connect(endpoint).await }
+            }))
+            .await
+            .map_err(|err| Error::Unknown(err.to_string()))?;
+
+        Ok(Self { channel })
+    }
+
+    /// check_dfdaemon_download checks that dfdaemon's download service is serving, via the
+    /// standard gRPC health checking protocol.
+    pub async fn check_dfdaemon_download(&self) -> Result<()> {
+        let mut client = HealthGrpcClient::new(self.channel.clone());
+        client
+            .check(HealthCheckRequest {
+                service: "dfdaemon.v2.DfdaemonDownload".to_string(),
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// version_dfdaemon_download extends the health probe by fetching dfdaemon's reported
+    /// version string, so the caller can check protocol compatibility before handing out a
+    /// download client. This dials `GetDaemonInfo`, part of the `Dfdaemon` service, over
+    /// the same channel as the `DfdaemonDownload` health check; it isn't confirmed that
+    /// dfdaemon also serves `Dfdaemon` on this download socket, so callers must treat an
+    /// error here (for example `Unimplemented`) as "version unknown", not as a broken
+    /// connection.
+    pub async fn version_dfdaemon_download(&self) -> Result<String> {
+        let mut client = DfdaemonClient::new(self.channel.clone());
+        let response = client
+            .get_daemon_info(GetDaemonInfoRequest {})
+            .await?
+            .into_inner();
+
+        Ok(response.version)
+    }
+}
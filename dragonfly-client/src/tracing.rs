@@ -0,0 +1,30 @@
+/*
+ *     Copyright 2024 The Dragonfly Authors
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::path::Path;
+use tracing::Level;
+use tracing_subscriber::{EnvFilter, FmtSubscriber};
+
+/// init_tracing initializes the global tracing subscriber for a Dragonfly client binary,
+/// logging at the given level to stdout.
+pub fn init_tracing(_name: &str, _log_dir: &Path, log_level: Level, console: bool) {
+    let subscriber = FmtSubscriber::builder()
+        .with_env_filter(EnvFilter::new(log_level.to_string()))
+        .with_ansi(console)
+        .finish();
+
+    let _ = tracing::subscriber::set_global_default(subscriber);
+}